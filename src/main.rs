@@ -1,44 +1,173 @@
 use std::fs;
-use std::path::PathBuf;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
 use std::process::{Child, Command};
 use std::sync::{Arc, Mutex};
 use std::thread;
 
+use global_hotkey::hotkey::HotKey;
+use global_hotkey::{GlobalHotKeyEvent, GlobalHotKeyManager, HotKeyState};
 use serde::{Deserialize, Serialize};
-use tray_icon::menu::{CheckMenuItem, Menu, MenuItem, PredefinedMenuItem};
+use tray_icon::menu::accelerator::Accelerator;
+use tray_icon::menu::{CheckMenuItem, Menu, MenuItem, PredefinedMenuItem, Submenu};
 use tray_icon::TrayIconBuilder;
-use winit::event_loop::{ControlFlow, EventLoopBuilder};
+use winit::event::Event;
+use winit::event_loop::{ControlFlow, EventLoopBuilder, EventLoopProxy};
 #[cfg(target_os = "macos")]
 use winit::platform::macos::EventLoopBuilderExtMacOS;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-enum Editor {
-    Zed,
-    Vscode,
-    Sublime,
+/// User event used to wake the event loop so the tray UI can be re-synced to
+/// the shared [`AppState`] after it is mutated off the main thread (e.g. by the
+/// IPC control channel).
+#[derive(Debug, Clone, Copy)]
+enum UiEvent {
+    Refresh,
 }
 
-impl Editor {
-    fn to_bin_path(&self) -> &str {
-        match self {
-            Editor::Zed => "/usr/local/bin/zed",
-            Editor::Vscode => "/usr/local/bin/code",
-            Editor::Sublime => "/Applications/Sublime Text.app/Contents/SharedSupport/bin/subl",
-        }
-    }
+/// A user-configurable editor entry. The `name` is what appears in the tray
+/// menu, `bin_path` is passed to the server via `--zed-bin`, and `extra_args`
+/// are forwarded verbatim after it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct EditorProfile {
+    name: String,
+    bin_path: PathBuf,
+    #[serde(default)]
+    extra_args: Vec<String>,
+    /// Optional in-menu accelerator, e.g. `"CmdOrCtrl+Shift+1"`.
+    #[serde(default)]
+    accelerator: Option<String>,
 }
 
+/// Default global hotkey registered when the config does not override it.
+const DEFAULT_GLOBAL_HOTKEY: &str = "CmdOrCtrl+Shift+R";
+/// Default in-menu accelerator for the start/stop toggle.
+const DEFAULT_TOGGLE_ACCELERATOR: &str = "CmdOrCtrl+Shift+S";
+/// Host the server binds to when none is configured.
+const DEFAULT_HOST: &str = "127.0.0.1";
+/// Ports offered in the Server Settings submenu (rmate's default first).
+const PORT_CHOICES: &[u16] = &[52698, 52699, 7000];
+
 struct AppState {
     server: Option<Child>,
-    editor: Editor,
+    editors: Vec<EditorProfile>,
+    selected: usize,
+    /// Whether the supervisor should restart the server after an unexpected
+    /// exit.
+    auto_restart: bool,
+    /// Set when the server died on its own rather than being stopped by us.
+    crashed: bool,
+    /// Set when the supervisor has exhausted its restart attempts and stopped
+    /// retrying, so the tooltip can distinguish this from the retrying state.
+    gave_up: bool,
+    /// Consecutive restart attempts since the last healthy check.
+    failures: u32,
+    /// Persisted accelerator/hotkey specs, round-tripped through the config.
+    toggle_accelerator: Option<String>,
+    global_hotkey: Option<String>,
+    /// Bind options forwarded to the server process.
+    port: Option<u16>,
+    host: Option<String>,
+    allow_remote: bool,
+    /// Set when the configured port was already in use at launch.
+    port_conflict: bool,
 }
 
 #[derive(Serialize, Deserialize)]
 struct AppConfig {
-    editor: Editor,
+    editors: Vec<EditorProfile>,
+    selected: String,
+    #[serde(default)]
+    auto_restart: bool,
+    /// Accelerator string for the start/stop toggle menu item.
+    #[serde(default)]
+    toggle_accelerator: Option<String>,
+    /// Global hotkey that toggles the server without opening the menu.
+    #[serde(default)]
+    global_hotkey: Option<String>,
+    /// Port the server listens on. `None` leaves the server's own default.
+    #[serde(default)]
+    port: Option<u16>,
+    /// Interface the server binds to. `None` leaves the server's own default.
+    #[serde(default)]
+    host: Option<String>,
+    /// Allow connections from non-loopback hosts.
+    #[serde(default)]
+    allow_remote: bool,
+}
+
+/// Maximum number of consecutive auto-restart attempts before the supervisor
+/// gives up and leaves the server stopped.
+const MAX_RESTART_ATTEMPTS: u32 = 5;
+
+/// Known editors and the binary names/paths we probe for each. Absolute entries
+/// are checked directly; bare names are resolved against `$PATH` like `which`.
+fn editor_candidates() -> Vec<(&'static str, Vec<&'static str>)> {
+    vec![
+        (
+            "Zed",
+            vec!["/usr/local/bin/zed", "/opt/homebrew/bin/zed", "zed"],
+        ),
+        (
+            "VS Code",
+            vec!["/usr/local/bin/code", "/opt/homebrew/bin/code", "code"],
+        ),
+        (
+            "Sublime Text",
+            vec![
+                "/Applications/Sublime Text.app/Contents/SharedSupport/bin/subl",
+                "/usr/local/bin/subl",
+                "subl",
+            ],
+        ),
+        (
+            "Neovim",
+            vec!["/usr/local/bin/nvim", "/opt/homebrew/bin/nvim", "nvim"],
+        ),
+        (
+            "Helix",
+            vec!["/usr/local/bin/hx", "/opt/homebrew/bin/hx", "hx"],
+        ),
+    ]
+}
+
+/// Resolve a candidate to a concrete binary path. Absolute candidates must
+/// exist on disk; bare names are looked up across `$PATH`.
+fn resolve_binary(candidate: &str) -> Option<PathBuf> {
+    let path = Path::new(candidate);
+    if path.is_absolute() {
+        return path.exists().then(|| path.to_path_buf());
+    }
+
+    std::env::var_os("PATH").and_then(|paths| {
+        std::env::split_paths(&paths).find_map(|dir| {
+            let full = dir.join(candidate);
+            full.exists().then_some(full)
+        })
+    })
 }
 
-fn load_icon(base_path: &PathBuf, icon_name: &str) -> tray_icon::Icon {
+/// Probe the known candidates and return a profile for each editor whose
+/// binary is actually installed.
+fn detect_editors() -> Vec<EditorProfile> {
+    editor_candidates()
+        .into_iter()
+        .filter_map(|(name, candidates)| {
+            candidates
+                .iter()
+                .find_map(|candidate| resolve_binary(candidate))
+                .map(|bin_path| EditorProfile {
+                    name: name.to_string(),
+                    bin_path,
+                    extra_args: Vec::new(),
+                    accelerator: None,
+                })
+        })
+        .collect()
+}
+
+fn load_icon(base_path: &Path, icon_name: &str) -> tray_icon::Icon {
     let path = base_path.join("icons").join(icon_name);
     let (icon_rgba, icon_width, icon_height) = {
         let image = image::open(&path)
@@ -51,25 +180,115 @@ fn load_icon(base_path: &PathBuf, icon_name: &str) -> tray_icon::Icon {
     tray_icon::Icon::from_rgba(icon_rgba, icon_width, icon_height).expect("Failed to open icon")
 }
 
-fn start_server(state: &mut AppState, resources_path: &PathBuf) {
+/// Re-sync the tray UI (toggle label, icon, editor check marks) to the current
+/// [`AppState`]. Called after any state change, whether from the menu or the
+/// IPC control channel.
+fn refresh_ui(
+    state: &AppState,
+    toggle_mi: &MenuItem,
+    editor_items: &[CheckMenuItem],
+    tray_icon: &tray_icon::TrayIcon,
+    icon_on: &tray_icon::Icon,
+    icon_off: &tray_icon::Icon,
+) {
+    if state.server.is_some() {
+        toggle_mi.set_text("Stop Server");
+        let _ = tray_icon.set_icon(Some(icon_on.clone()));
+    } else {
+        toggle_mi.set_text("Start Server");
+        let _ = tray_icon.set_icon(Some(icon_off.clone()));
+    }
+    for (i, item) in editor_items.iter().enumerate() {
+        item.set_checked(i == state.selected);
+    }
+    let tooltip = if state.port_conflict {
+        format!(
+            "RMate Server — port {} in use",
+            state.port.map(|p| p.to_string()).unwrap_or_default()
+        )
+    } else if state.gave_up {
+        "RMate Server — crashed, gave up".to_string()
+    } else if state.crashed {
+        "RMate Server — crashed, retrying".to_string()
+    } else {
+        "RMate Server".to_string()
+    };
+    let _ = tray_icon.set_tooltip(Some(&tooltip));
+}
+
+/// Whether `port` is currently in use on `host`. A successful bind means the
+/// port is free; the listener is dropped immediately.
+fn port_in_use(host: &str, port: u16) -> bool {
+    TcpListener::bind((host, port)).is_err()
+}
+
+/// Wait for the configured port to be released before restarting, falling back
+/// to a fixed grace period when no port is configured. Takes the values rather
+/// than a locked [`AppState`] so the IPC path can release the mutex while it
+/// sleeps.
+fn wait_for_port_release(port: Option<u16>, host: Option<&str>) {
+    match port {
+        Some(port) => {
+            let host = host.unwrap_or(DEFAULT_HOST);
+            for _ in 0..20 {
+                if !port_in_use(host, port) {
+                    return;
+                }
+                thread::sleep(std::time::Duration::from_millis(100));
+            }
+        }
+        None => thread::sleep(std::time::Duration::from_millis(500)),
+    }
+}
+
+fn start_server(state: &mut AppState, resources_path: &Path) {
     if state.server.is_some() {
         return;
     }
 
-    let editor_path = state.editor.to_bin_path();
+    let Some(editor) = state.editors.get(state.selected) else {
+        eprintln!("No editor selected; cannot start server");
+        return;
+    };
+
+    // Refuse to spawn onto an occupied port so we can report it rather than
+    // leaving a dead tray behind a silently failed child.
+    if let Some(port) = state.port {
+        let host = state.host.as_deref().unwrap_or(DEFAULT_HOST);
+        if port_in_use(host, port) {
+            eprintln!("Port {} on {} is already in use", port, host);
+            state.port_conflict = true;
+            return;
+        }
+    }
+    state.port_conflict = false;
+
     let server_path = resources_path.join("bin").join("zed-rmate-server");
 
-    match Command::new(&server_path)
-        .arg("--zed-bin")
-        .arg(editor_path)
-        .spawn()
-    {
+    let mut command = Command::new(&server_path);
+    command.arg("--zed-bin").arg(&editor.bin_path);
+    command.args(&editor.extra_args);
+    if let Some(host) = &state.host {
+        command.arg("--host").arg(host);
+    }
+    if let Some(port) = state.port {
+        command.arg("--port").arg(port.to_string());
+    }
+    if state.allow_remote {
+        command.arg("--allow-remote");
+    }
+
+    match command.spawn() {
         Ok(child) => {
             state.server = Some(child);
-            println!("Server started for {:?}", state.editor);
+            println!("Server started for {}", editor.name);
         }
         Err(e) => {
-            eprintln!("Failed to start server for {}: {}", editor_path, e);
+            eprintln!(
+                "Failed to start server for {}: {}",
+                editor.bin_path.display(),
+                e
+            );
         }
     }
 }
@@ -94,12 +313,34 @@ fn get_config_path() -> Option<PathBuf> {
 }
 
 fn load_config() -> AppConfig {
-    get_config_path()
+    let mut config = get_config_path()
         .and_then(|path| fs::read_to_string(path).ok())
-        .and_then(|content| serde_json::from_str(&content).ok())
-        .unwrap_or(AppConfig {
-            editor: Editor::Zed, // Default editor
-        })
+        .and_then(|content| serde_json::from_str::<AppConfig>(&content).ok())
+        .unwrap_or_else(|| AppConfig {
+            editors: Vec::new(),
+            selected: String::new(),
+            auto_restart: false,
+            toggle_accelerator: None,
+            global_hotkey: None,
+            port: None,
+            host: None,
+            allow_remote: false,
+        });
+
+    // Surface editors that are installed but not yet known to the config.
+    for detected in detect_editors() {
+        if !config.editors.iter().any(|e| e.name == detected.name) {
+            config.editors.push(detected);
+        }
+    }
+
+    if config.selected.is_empty() {
+        if let Some(first) = config.editors.first() {
+            config.selected = first.name.clone();
+        }
+    }
+
+    config
 }
 
 fn save_config(config: &AppConfig) {
@@ -111,11 +352,348 @@ fn save_config(config: &AppConfig) {
     }
 }
 
+/// Snapshot the persistable parts of the running state into an [`AppConfig`].
+fn config_from_state(state: &AppState) -> AppConfig {
+    AppConfig {
+        editors: state.editors.clone(),
+        selected: state
+            .editors
+            .get(state.selected)
+            .map(|e| e.name.clone())
+            .unwrap_or_default(),
+        auto_restart: state.auto_restart,
+        toggle_accelerator: state.toggle_accelerator.clone(),
+        global_hotkey: state.global_hotkey.clone(),
+        port: state.port,
+        host: state.host.clone(),
+        allow_remote: state.allow_remote,
+    }
+}
+
+/// Parse an accelerator spec string, warning (not crashing) on a bad value.
+fn parse_accelerator(spec: &str) -> Option<Accelerator> {
+    match spec.parse::<Accelerator>() {
+        Ok(accel) => Some(accel),
+        Err(e) => {
+            eprintln!("Ignoring invalid accelerator {:?}: {}", spec, e);
+            None
+        }
+    }
+}
+
+/// Toggle the server on/off, clearing crash bookkeeping so the supervisor
+/// treats the new lifecycle as a fresh start. Shared by the menu item and the
+/// global hotkey; the IPC `start`/`stop` commands clear the same bookkeeping
+/// themselves.
+fn toggle_server(state: &mut AppState, resources_path: &Path) {
+    state.crashed = false;
+    state.gave_up = false;
+    state.failures = 0;
+    if state.server.is_some() {
+        stop_server(state);
+    } else {
+        start_server(state, resources_path);
+    }
+}
+
+/// Stop the server (if running), record the new editor selection, and persist
+/// it. Returns whether the server was running and therefore needs restarting.
+/// Split out so the menu and IPC paths can share the bookkeeping while managing
+/// the lock around the restart sleep differently.
+fn apply_editor_selection(state: &mut AppState, idx: usize) -> bool {
+    let was_running = state.server.is_some();
+    if was_running {
+        stop_server(state);
+    }
+
+    state.selected = idx;
+    save_config(&config_from_state(state));
+    println!("Switched editor to {}", state.editors[idx].name);
+    was_running
+}
+
+/// Switch the active editor, persisting the choice and restarting the server if
+/// it was running so the new binary takes effect. Used by the menu handler,
+/// which holds the state lock for the whole call.
+fn switch_editor(state: &mut AppState, idx: usize, resources_path: &Path) {
+    if idx >= state.editors.len() || idx == state.selected {
+        return;
+    }
+
+    if apply_editor_selection(state, idx) {
+        // Wait for the configured port to be released before rebinding.
+        wait_for_port_release(state.port, state.host.as_deref());
+        start_server(state, resources_path);
+    }
+}
+
+/// Restart the server after a bind-option change so the new port/host/remote
+/// flags take effect, but only if it was already running. Waits for the old
+/// port to be released first, mirroring [`switch_editor`].
+fn restart_if_running(state: &mut AppState, resources_path: &Path) {
+    if state.server.is_none() {
+        return;
+    }
+    stop_server(state);
+    wait_for_port_release(state.port, state.host.as_deref());
+    start_server(state, resources_path);
+}
+
+/// Spawn the supervisor thread that reaps the child server. When the process
+/// exits unexpectedly it flips the tray back to the stopped state and, if
+/// auto-restart is enabled, restarts it with exponential backoff up to
+/// [`MAX_RESTART_ATTEMPTS`] consecutive failures.
+fn spawn_supervisor(
+    state: Arc<Mutex<AppState>>,
+    resources_path: PathBuf,
+    proxy: EventLoopProxy<UiEvent>,
+) {
+    thread::spawn(move || loop {
+        thread::sleep(std::time::Duration::from_secs(2));
+
+        let mut guard = state.lock().unwrap();
+        let exited = match guard.server.as_mut() {
+            Some(child) => match child.try_wait() {
+                Ok(Some(_status)) => true,
+                Ok(None) => {
+                    // Still alive; clear any lingering crash state once it has
+                    // survived a full check cycle.
+                    if guard.crashed {
+                        guard.crashed = false;
+                        guard.gave_up = false;
+                        guard.failures = 0;
+                        let _ = proxy.send_event(UiEvent::Refresh);
+                    }
+                    false
+                }
+                Err(e) => {
+                    eprintln!("Failed to poll server process: {}", e);
+                    true
+                }
+            },
+            // A crash with no child and no give-up means the last restart
+            // attempt failed to spawn; count it and try again.
+            None => guard.crashed && !guard.gave_up,
+        };
+
+        if !exited {
+            continue;
+        }
+
+        // Reap the dead child and surface the crash in the tray.
+        if guard.server.take().is_some() {
+            eprintln!("Server exited unexpectedly");
+        }
+        guard.crashed = true;
+
+        if guard.auto_restart && guard.failures < MAX_RESTART_ATTEMPTS {
+            guard.failures += 1;
+            let backoff = std::time::Duration::from_millis(500 * (1 << (guard.failures - 1)))
+                .min(std::time::Duration::from_secs(30));
+            drop(guard);
+            let _ = proxy.send_event(UiEvent::Refresh);
+
+            thread::sleep(backoff);
+
+            let mut guard = state.lock().unwrap();
+            // Only restart if the user hasn't taken over in the meantime.
+            if guard.server.is_none() && guard.crashed {
+                start_server(&mut guard, &resources_path);
+                if guard.server.is_some() {
+                    println!("Auto-restarted server (attempt {})", guard.failures);
+                }
+            }
+            drop(guard);
+            let _ = proxy.send_event(UiEvent::Refresh);
+        } else {
+            // No retry pending — either auto-restart is disabled or the attempts
+            // are exhausted — so mark the terminal state and stop the tooltip
+            // claiming we're still retrying.
+            guard.gave_up = true;
+            drop(guard);
+            let _ = proxy.send_event(UiEvent::Refresh);
+        }
+    });
+}
+
+/// Path of the Unix domain socket used by the headless CLI to drive a running
+/// tray instance. Derived alongside [`get_config_path`] under the config dir.
+fn get_socket_path() -> Option<PathBuf> {
+    dirs_next::config_dir().map(|mut path| {
+        path.push("rmate-server");
+        fs::create_dir_all(&path).ok();
+        path.push("rmate-server.sock");
+        path
+    })
+}
+
+/// Execute a control command against the shared state and return the reply line
+/// sent back to the CLI client.
+fn dispatch_command(command: &str, state: &Arc<Mutex<AppState>>, resources_path: &Path) -> String {
+    let mut parts = command.split_whitespace();
+    match parts.next() {
+        Some("start") => {
+            let mut state = state.lock().unwrap();
+            state.crashed = false;
+            state.gave_up = false;
+            state.failures = 0;
+            start_server(&mut state, resources_path);
+            if state.server.is_some() {
+                "Server started\n".to_string()
+            } else {
+                "Failed to start server\n".to_string()
+            }
+        }
+        Some("stop") => {
+            let mut state = state.lock().unwrap();
+            stop_server(&mut state);
+            state.crashed = false;
+            state.gave_up = false;
+            state.failures = 0;
+            "Server stopped\n".to_string()
+        }
+        Some("status") => {
+            let state = state.lock().unwrap();
+            let editor = state
+                .editors
+                .get(state.selected)
+                .map(|e| e.name.as_str())
+                .unwrap_or("none");
+            if state.server.is_some() {
+                format!("running ({})\n", editor)
+            } else {
+                format!("stopped ({})\n", editor)
+            }
+        }
+        Some("use") => {
+            let name = parts.collect::<Vec<_>>().join(" ");
+            // Resolve the target and stop the old server under a short-lived
+            // lock, then release it before the port-release sleep so the tray
+            // event loop isn't frozen for the duration of an IPC switch.
+            let (restart, port, host, reply) = {
+                let mut guard = state.lock().unwrap();
+                match guard
+                    .editors
+                    .iter()
+                    .position(|e| e.name.eq_ignore_ascii_case(&name))
+                {
+                    Some(idx) if idx != guard.selected => {
+                        let was_running = apply_editor_selection(&mut guard, idx);
+                        let reply = format!("Editor set to {}\n", guard.editors[idx].name);
+                        (was_running, guard.port, guard.host.clone(), reply)
+                    }
+                    Some(idx) => (
+                        false,
+                        None,
+                        None,
+                        format!("Editor set to {}\n", guard.editors[idx].name),
+                    ),
+                    None => (false, None, None, format!("Unknown editor: {}\n", name)),
+                }
+            };
+            if restart {
+                wait_for_port_release(port, host.as_deref());
+                let mut guard = state.lock().unwrap();
+                start_server(&mut guard, resources_path);
+            }
+            reply
+        }
+        Some(other) => format!("Unknown command: {}\n", other),
+        None => "No command given\n".to_string(),
+    }
+}
+
+/// Handle a single CLI connection: read one command line, act on it, reply, and
+/// ask the event loop to re-sync the tray UI.
+fn handle_ipc_client(
+    mut stream: UnixStream,
+    state: &Arc<Mutex<AppState>>,
+    resources_path: &Path,
+    proxy: &EventLoopProxy<UiEvent>,
+) {
+    let Ok(read_half) = stream.try_clone() else {
+        return;
+    };
+    let mut reader = BufReader::new(read_half);
+    let mut line = String::new();
+    if reader.read_line(&mut line).is_err() {
+        return;
+    }
+
+    let reply = dispatch_command(line.trim(), state, resources_path);
+    let _ = stream.write_all(reply.as_bytes());
+    let _ = proxy.send_event(UiEvent::Refresh);
+}
+
+/// Spawn the IPC server thread that listens for CLI commands on the control
+/// socket. Any stale socket left by a previous run is removed first.
+fn spawn_ipc_server(
+    state: Arc<Mutex<AppState>>,
+    resources_path: PathBuf,
+    proxy: EventLoopProxy<UiEvent>,
+) {
+    let Some(path) = get_socket_path() else {
+        return;
+    };
+    let _ = fs::remove_file(&path);
+
+    let listener = match UnixListener::bind(&path) {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("Failed to bind control socket at {:?}: {}", path, e);
+            return;
+        }
+    };
+
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            handle_ipc_client(stream, &state, &resources_path, &proxy);
+        }
+    });
+}
+
+/// Connect to a running tray instance, forward the CLI command, print the
+/// reply, and return a process exit code.
+fn run_client(args: &[String]) -> i32 {
+    let Some(path) = get_socket_path() else {
+        eprintln!("Could not determine control socket path");
+        return 1;
+    };
+
+    let mut stream = match UnixStream::connect(&path) {
+        Ok(stream) => stream,
+        Err(e) => {
+            eprintln!("Could not reach the tray app ({}). Is it running?", e);
+            return 1;
+        }
+    };
+
+    let command = args.join(" ");
+    if let Err(e) = writeln!(stream, "{}", command) {
+        eprintln!("Failed to send command: {}", e);
+        return 1;
+    }
+
+    let mut reply = String::new();
+    let _ = stream.read_to_string(&mut reply);
+    print!("{}", reply);
+    0
+}
+
 fn main() {
+    // When invoked with a subcommand, act as a CLI client driving the running
+    // tray instance over the control socket instead of spawning a new tray.
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Some(first) = args.first() {
+        if matches!(first.as_str(), "start" | "stop" | "status" | "use") {
+            std::process::exit(run_client(&args));
+        }
+    }
+
     env_logger::init();
 
     let config = load_config();
-    let mut event_loop_builder = EventLoopBuilder::new();
+    let mut event_loop_builder = EventLoopBuilder::<UiEvent>::with_user_event();
 
     #[cfg(target_os = "macos")]
     event_loop_builder.with_activation_policy(winit::platform::macos::ActivationPolicy::Accessory);
@@ -141,30 +719,84 @@ fn main() {
     let icon_on = load_icon(&resources_path, "icon.png");
     let icon_off = load_icon(&resources_path, "icon-off.png");
 
+    let selected_idx = config
+        .editors
+        .iter()
+        .position(|e| e.name == config.selected)
+        .unwrap_or(0);
+
     // App state shared between threads
     let app_state = Arc::new(Mutex::new(AppState {
         server: None,
-        editor: config.editor,
+        editors: config.editors.clone(),
+        selected: selected_idx,
+        auto_restart: config.auto_restart,
+        crashed: false,
+        gave_up: false,
+        failures: 0,
+        toggle_accelerator: config.toggle_accelerator.clone(),
+        global_hotkey: config.global_hotkey.clone(),
+        port: config.port,
+        host: config.host.clone(),
+        allow_remote: config.allow_remote,
+        port_conflict: false,
     }));
 
     // --- Menu setup ---
     let menu = Menu::new();
 
-    let toggle_server_mi = MenuItem::new("Start Server", true, None);
+    let toggle_accel = parse_accelerator(
+        config
+            .toggle_accelerator
+            .as_deref()
+            .unwrap_or(DEFAULT_TOGGLE_ACCELERATOR),
+    );
+    let toggle_server_mi = MenuItem::new("Start Server", true, toggle_accel);
     menu.append_items(&[&toggle_server_mi, &PredefinedMenuItem::separator()])
         .unwrap();
 
-    let zed_mi = CheckMenuItem::new("Zed", true, config.editor == Editor::Zed, None);
-    let vscode_mi = CheckMenuItem::new("VS Code", true, config.editor == Editor::Vscode, None);
-    let sublime_mi =
-        CheckMenuItem::new("Sublime Text", true, config.editor == Editor::Sublime, None);
-    menu.append_items(&[
-        &zed_mi,
-        &vscode_mi,
-        &sublime_mi,
-        &PredefinedMenuItem::separator(),
-    ])
-    .unwrap();
+    // Build the editor list dynamically from the configured profiles. Editors
+    // whose binary is missing are shown greyed out rather than selectable.
+    let editor_items: Vec<CheckMenuItem> = config
+        .editors
+        .iter()
+        .enumerate()
+        .map(|(i, editor)| {
+            let installed = editor.bin_path.exists();
+            let accel = editor.accelerator.as_deref().and_then(parse_accelerator);
+            CheckMenuItem::new(&editor.name, installed, installed && i == selected_idx, accel)
+        })
+        .collect();
+    for item in &editor_items {
+        menu.append(item).unwrap();
+    }
+    menu.append(&PredefinedMenuItem::separator()).unwrap();
+
+    // Server Settings submenu: a port selector plus a remote-access toggle.
+    // The offered ports come from `PORT_CHOICES`; the configured one is checked,
+    // and re-selecting the checked port clears the choice to fall back on the
+    // server's own default.
+    let server_settings = Submenu::new("Server Settings", true);
+    let port_items: Vec<CheckMenuItem> = PORT_CHOICES
+        .iter()
+        .map(|port| {
+            CheckMenuItem::new(format!("Port {}", port), true, config.port == Some(*port), None)
+        })
+        .collect();
+    for item in &port_items {
+        server_settings.append(item).unwrap();
+    }
+    server_settings
+        .append(&PredefinedMenuItem::separator())
+        .unwrap();
+    let allow_remote_mi = CheckMenuItem::new("Allow Remote", true, config.allow_remote, None);
+    server_settings.append(&allow_remote_mi).unwrap();
+    menu.append_items(&[&server_settings, &PredefinedMenuItem::separator()])
+        .unwrap();
+
+    let auto_restart_mi = CheckMenuItem::new("Auto-restart", true, config.auto_restart, None);
+    menu.append_items(&[&auto_restart_mi, &PredefinedMenuItem::separator()])
+        .unwrap();
 
     let quit_mi = MenuItem::new("Quit", true, None);
     menu.append(&quit_mi).unwrap();
@@ -182,80 +814,151 @@ fn main() {
     {
         let mut state = app_state.lock().unwrap();
         start_server(&mut state, &resources_path);
-        if state.server.is_some() {
-            toggle_server_mi.set_text("Stop Server");
-            tray_icon.set_icon(Some(icon_on.clone())).unwrap();
+        // Sync the whole UI (including the tooltip) so a port conflict at launch
+        // is surfaced instead of leaving a dead tray in its default state.
+        refresh_ui(
+            &state,
+            &toggle_server_mi,
+            &editor_items,
+            &tray_icon,
+            &icon_on,
+            &icon_off,
+        );
+    }
+
+    // Spawn the control socket listener so `rmate-server-app start|stop|...`
+    // can drive this instance. Refreshes are delivered back as user events.
+    spawn_ipc_server(
+        Arc::clone(&app_state),
+        resources_path.clone(),
+        event_loop.create_proxy(),
+    );
+
+    // Supervise the child server and reflect crashes/auto-restarts in the tray.
+    spawn_supervisor(
+        Arc::clone(&app_state),
+        resources_path.clone(),
+        event_loop.create_proxy(),
+    );
+
+    // Register the configurable global hotkey that toggles the server from
+    // anywhere. The manager must outlive the event loop to stay registered.
+    let hotkey_manager = GlobalHotKeyManager::new().ok();
+    let toggle_hotkey = config
+        .global_hotkey
+        .as_deref()
+        .unwrap_or(DEFAULT_GLOBAL_HOTKEY)
+        .parse::<HotKey>()
+        .ok();
+    if let (Some(manager), Some(hotkey)) = (&hotkey_manager, &toggle_hotkey) {
+        if let Err(e) = manager.register(*hotkey) {
+            eprintln!("Failed to register global hotkey: {}", e);
         }
     }
+    let hotkey_channel = GlobalHotKeyEvent::receiver();
 
     let menu_channel = tray_icon::menu::MenuEvent::receiver();
 
-    let _ = event_loop.run(move |_event, event_loop| {
+    let _ = event_loop.run(move |event, event_loop| {
         event_loop.set_control_flow(ControlFlow::Wait);
 
+        if let Event::UserEvent(UiEvent::Refresh) = event {
+            let state = app_state.lock().unwrap();
+            refresh_ui(
+                &state,
+                &toggle_server_mi,
+                &editor_items,
+                &tray_icon,
+                &icon_on,
+                &icon_off,
+            );
+        }
+
+        // A global-hotkey press toggles the server through the same path as the
+        // menu item.
+        if let Ok(hotkey_event) = hotkey_channel.try_recv() {
+            let _ = &hotkey_manager; // keep the manager registered for the loop's lifetime
+            if hotkey_event.state == HotKeyState::Pressed
+                && toggle_hotkey.as_ref().is_some_and(|hk| hk.id() == hotkey_event.id)
+            {
+                let mut state = app_state.lock().unwrap();
+                toggle_server(&mut state, &resources_path);
+                refresh_ui(
+                    &state,
+                    &toggle_server_mi,
+                    &editor_items,
+                    &tray_icon,
+                    &icon_on,
+                    &icon_off,
+                );
+            }
+        }
+
         if let Ok(event) = menu_channel.try_recv() {
             let mut state = app_state.lock().unwrap();
 
             if event.id == toggle_server_mi.id() {
-                if state.server.is_some() {
-                    stop_server(&mut state);
-                    toggle_server_mi.set_text("Start Server");
-                    tray_icon.set_icon(Some(icon_off.clone())).unwrap();
+                toggle_server(&mut state, &resources_path);
+                refresh_ui(
+                    &state,
+                    &toggle_server_mi,
+                    &editor_items,
+                    &tray_icon,
+                    &icon_on,
+                    &icon_off,
+                );
+            } else if event.id == auto_restart_mi.id() {
+                state.auto_restart = auto_restart_mi.is_checked();
+                save_config(&config_from_state(&state));
+            } else if let Some(idx) = editor_items.iter().position(|mi| mi.id() == &event.id) {
+                switch_editor(&mut state, idx, &resources_path);
+                refresh_ui(
+                    &state,
+                    &toggle_server_mi,
+                    &editor_items,
+                    &tray_icon,
+                    &icon_on,
+                    &icon_off,
+                );
+            } else if let Some(idx) = port_items.iter().position(|mi| mi.id() == &event.id) {
+                let chosen = PORT_CHOICES[idx];
+                // Toggle the chosen port: re-selecting the active one reverts to
+                // the server's default (no `--port` forwarded).
+                state.port = if state.port == Some(chosen) {
+                    None
                 } else {
-                    start_server(&mut state, &resources_path);
-                    toggle_server_mi.set_text("Stop Server");
-                    tray_icon.set_icon(Some(icon_on.clone())).unwrap();
-                }
-            } else if event.id == zed_mi.id()
-                || event.id == vscode_mi.id()
-                || event.id == sublime_mi.id()
-            {
-                let new_editor = if event.id == zed_mi.id() {
-                    Editor::Zed
-                } else if event.id == vscode_mi.id() {
-                    Editor::Vscode
-                } else {
-                    Editor::Sublime
+                    Some(chosen)
                 };
-
-                if state.editor != new_editor {
-                    let was_running = state.server.is_some();
-                    if was_running {
-                        stop_server(&mut state);
-                    }
-
-                    // Uncheck old editor
-                    match state.editor {
-                        Editor::Zed => zed_mi.set_checked(false),
-                        Editor::Vscode => vscode_mi.set_checked(false),
-                        Editor::Sublime => sublime_mi.set_checked(false),
-                    }
-
-                    // Update state and check new editor
-                    state.editor = new_editor;
-                    save_config(&AppConfig { editor: new_editor });
-                    match new_editor {
-                        Editor::Zed => zed_mi.set_checked(true),
-                        Editor::Vscode => vscode_mi.set_checked(true),
-                        Editor::Sublime => sublime_mi.set_checked(true),
-                    }
-                    println!("Switched editor to {:?}", new_editor);
-
-                    if was_running {
-                        // Give the port a moment to be released
-                        thread::sleep(std::time::Duration::from_millis(500));
-                        start_server(&mut state, &resources_path);
-                    }
-                } else {
-                    // If the user clicks the already selected editor, re-check it
-                    match state.editor {
-                        Editor::Zed => zed_mi.set_checked(true),
-                        Editor::Vscode => vscode_mi.set_checked(true),
-                        Editor::Sublime => sublime_mi.set_checked(true),
-                    }
+                for (i, item) in port_items.iter().enumerate() {
+                    item.set_checked(state.port == Some(PORT_CHOICES[i]));
                 }
+                restart_if_running(&mut state, &resources_path);
+                save_config(&config_from_state(&state));
+                refresh_ui(
+                    &state,
+                    &toggle_server_mi,
+                    &editor_items,
+                    &tray_icon,
+                    &icon_on,
+                    &icon_off,
+                );
+            } else if event.id == allow_remote_mi.id() {
+                state.allow_remote = allow_remote_mi.is_checked();
+                restart_if_running(&mut state, &resources_path);
+                save_config(&config_from_state(&state));
+                refresh_ui(
+                    &state,
+                    &toggle_server_mi,
+                    &editor_items,
+                    &tray_icon,
+                    &icon_on,
+                    &icon_off,
+                );
             } else if event.id == quit_mi.id() {
                 stop_server(&mut state);
+                if let Some(path) = get_socket_path() {
+                    let _ = fs::remove_file(path);
+                }
                 event_loop.exit();
             }
         }